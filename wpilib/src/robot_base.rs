@@ -31,8 +31,13 @@ except according to those terms.
 */
 
 use super::{ds::*, observe};
+use std::any::Any;
+use std::ffi::{CStr, CString};
+use std::fmt;
 use std::mem;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
 use std::time::Duration;
 use wpilib_sys::*;
 
@@ -42,9 +47,233 @@ pub enum RobotBaseInitError {
     AlreadyInit,
 }
 
+impl fmt::Display for RobotBaseInitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RobotBaseInitError::AlreadyInit => write!(f, "a RobotBase has already been created"),
+            RobotBaseInitError::HalInitFailed => {
+                let (code, message) = hal_last_error();
+                write!(f, "HAL_Initialize failed ({}): {}", code, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RobotBaseInitError {}
+
+/// Decode a HAL status code into its human-readable error message
+/// (e.g. "NO_AVAILABLE_RESOURCES", "SAMPLE_RATE_TOO_HIGH").
+///
+/// The C string the HAL hands back is thread-local and may be overwritten by
+/// the very next HAL call, so it is copied into an owned `String` immediately.
+///
+/// `hal_call!`/`HalResult` live in `wpilib_sys`, which isn't part of this
+/// checkout, so wiring their `Display` impl through these decoders isn't
+/// possible from here; `RobotBaseInitError::Display` above is this crate's
+/// own HAL-originated error path and uses `hal_last_error` for exactly the
+/// same purpose.
+pub fn hal_error_message(code: i32) -> String {
+    unsafe { CStr::from_ptr(HAL_GetErrorMessage(code)) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Fetch the status code and decoded message of the last error recorded by the HAL.
+pub fn hal_last_error() -> (i32, String) {
+    let mut status = HAL_USE_LAST_ERROR;
+    let message = unsafe { CStr::from_ptr(HAL_GetLastError(&mut status)) }
+        .to_string_lossy()
+        .into_owned();
+    (status, message)
+}
+
+/// A user robot program that [`RobotBase::start_robot`] can construct and run.
+///
+/// Implementors provide their own setup in `new` and their competition loop in
+/// `run`; `start_robot` takes care of initializing the `RobotBase`, starting
+/// competition, and guarding the loop against panics.
+pub trait RobotProgram: Sized {
+    /// Build the robot, given the freshly initialized `RobotBase`.
+    fn new(base: &RobotBase) -> Self;
+
+    /// Run the robot's competition loop. Returns once the robot program should exit.
+    fn run(&mut self);
+}
+
+/// Report an error to the Driver Station, with an optional source location.
+fn report_error(location: &str, details: &str) {
+    unsafe {
+        let details = CString::new(details).unwrap_or_default();
+        let location = CString::new(location).unwrap_or_default();
+        let stack = CString::default();
+        HAL_SendError(
+            1, // isError
+            1, // errorCode
+            0, // isLVCode
+            details.as_ptr(),
+            location.as_ptr(),
+            stack.as_ptr(),
+            1, // printMsg
+        );
+    }
+}
+
+/// Report a crash to the Driver Station console and print a backtrace, the
+/// way WPILib's `StartRobot` does when the user's code throws or aborts.
+fn report_crash(message: &str) {
+    eprintln!("{}", message);
+    eprintln!("{}", std::backtrace::Backtrace::force_capture());
+    report_error("", message);
+}
+
+/// Write a line to the Driver Station console ("riolog"), the only place
+/// output reliably surfaces once the robot is running untethered from a
+/// terminal.
+pub fn ds_console_line(line: &str) {
+    unsafe {
+        let line = CString::new(line).unwrap_or_default();
+        HAL_SendConsoleLine(line.as_ptr());
+    }
+}
+
+/// Print to the Driver Station console, `println!`-style.
+///
+/// Prefer this over `println!` for anything that must reach the driver: plain
+/// stdout from a background thread on the roboRIO can be lost entirely.
+#[macro_export]
+macro_rules! ds_println {
+    ($($arg:tt)*) => {
+        $crate::robot_base::ds_console_line(&format!($($arg)*))
+    };
+}
+
+/// Install a panic hook that prints the panic and a backtrace locally, then
+/// forwards the message and location to the Driver Station as an error.
+///
+/// This replaces (rather than chains onto) the default hook so a panic is
+/// reported exactly once: the default hook only prints a backtrace when
+/// `RUST_BACKTRACE` is set, but on a roboRIO deploy that's unlikely to be set,
+/// so a backtrace is captured unconditionally here.
+fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "<unknown location>".to_string());
+        let payload = panic_message(info.payload());
+
+        eprintln!("robot program panicked at {}: {}", location, payload);
+        eprintln!("{}", std::backtrace::Backtrace::force_capture());
+
+        report_error(&location, &format!("The robot program panicked: {}", payload));
+    }));
+    PANIC_HOOK_INSTALLED.store(true, Ordering::Release);
+}
+
+/// Render a caught panic's payload as a string for reporting.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Which kind of hardware (or simulator) the HAL is currently running on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RuntimeType {
+    RoboRio,
+    RoboRio2,
+    Simulation,
+}
+
+/// Returned by [`RobotBase::runtime_type`] when it can't determine the
+/// runtime: either the underlying HAL call failed, or it succeeded but
+/// returned a runtime code this crate doesn't recognize.
+#[derive(Debug)]
+pub enum RuntimeTypeError {
+    Hal(i32, String),
+    UnknownCode(i32),
+}
+
+impl fmt::Display for RuntimeTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeTypeError::Hal(code, message) => {
+                write!(f, "HAL_GetRuntimeType failed ({}): {}", code, message)
+            }
+            RuntimeTypeError::UnknownCode(code) => write!(
+                f,
+                "HAL_GetRuntimeType returned an unrecognized runtime code: {}",
+                code
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeTypeError {}
+
 // Should we give in and use lazy static?
 static ROBOT_INITED: AtomicBool = AtomicBool::new(false);
 
+/// Set once `install_panic_hook` has replaced the default panic hook, so
+/// `RobotBase::start_robot` knows whether a caught panic was already
+/// reported to the Driver Station or needs a fallback report.
+static PANIC_HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// FPGA timestamp at which the robot most recently became enabled, or `0` if
+/// it is not currently enabled. Backs `RobotBase::match_time`.
+static ENABLED_SINCE: AtomicU64 = AtomicU64::new(0);
+
+/// Record an enable/disable or autonomous/teleop edge, resetting the
+/// match-time baseline accordingly. Returns `false` if an enable edge
+/// couldn't be recorded (e.g. a transient `fpga_time` failure), so the
+/// caller can retry on the next poll instead of losing the edge silently.
+///
+/// Driven by `poll_enabled_state`, which is spawned from
+/// `RobotBase::start_competition`.
+pub(crate) fn note_enabled_edge(now_enabled: bool) -> bool {
+    if now_enabled {
+        match RobotBase::fpga_time() {
+            Ok(now) => {
+                ENABLED_SINCE.store(now, Ordering::Release);
+                true
+            }
+            Err(_) => false,
+        }
+    } else {
+        ENABLED_SINCE.store(0, Ordering::Release);
+        true
+    }
+}
+
+/// Poll the HAL's control word for enable/disable and autonomous/teleop
+/// edges, feeding them to `note_enabled_edge` so `RobotBase::match_time` has
+/// a baseline to report against. Spawned once by `RobotBase::start_competition`.
+fn poll_enabled_state() {
+    let mut was_enabled = false;
+    let mut was_autonomous = false;
+    loop {
+        let mut word: HAL_ControlWord = unsafe { mem::zeroed() };
+        if unsafe { HAL_GetControlWord(&mut word) } == 0 {
+            let enabled = word.enabled() != 0;
+            let autonomous = word.autonomous() != 0;
+            // Reset the baseline on enable, disable, and autonomous/teleop edges.
+            let is_edge = enabled != was_enabled || (enabled && autonomous != was_autonomous);
+            // Only adopt the new state once the edge is actually recorded; if
+            // it failed, leave was_enabled/was_autonomous stale so the next
+            // tick sees the same edge again instead of dropping it.
+            if !is_edge || note_enabled_edge(enabled) {
+                was_enabled = enabled;
+                was_autonomous = autonomous;
+            }
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
 #[derive(Debug)]
 pub struct RobotBase {}
 
@@ -66,6 +295,7 @@ impl RobotBase {
         usage::report(usage::resource_types::Language, unsafe {
             mem::transmute(*b"Rust")
         });
+        install_panic_hook();
         println!("\n********** Hardware Init **********\n");
         Ok(RobotBase {})
     }
@@ -74,6 +304,7 @@ impl RobotBase {
     /// Make sure your hardware and threads have been created, etc.
     pub fn start_competition() {
         observe::start();
+        thread::spawn(poll_enabled_state);
         println!("\n********** Robot program starting **********\n");
     }
 
@@ -139,6 +370,107 @@ impl RobotBase {
     pub fn battery_voltage() -> HalResult<f64> {
         hal_call!(HAL_GetVinVoltage())
     }
+
+    /// Returns whether the code is running on a roboRIO, a roboRIO 2, or the simulator.
+    ///
+    /// This lets code branch on sim vs. real hardware, e.g. to skip real-time loops
+    /// or to substitute mock hardware when simulating.
+    ///
+    /// Fails with [`RuntimeTypeError`] rather than panicking if the HAL call
+    /// itself fails, or if it returns a runtime code this crate doesn't
+    /// recognize (e.g. a future hardware revision) — callers that need this
+    /// to be fatal can still choose to `unwrap`/`expect`.
+    pub fn runtime_type() -> Result<RuntimeType, RuntimeTypeError> {
+        let code = hal_call!(HAL_GetRuntimeType()).map_err(|_| {
+            let (code, message) = hal_last_error();
+            RuntimeTypeError::Hal(code, message)
+        })?;
+        match code {
+            0 => Ok(RuntimeType::RoboRio),
+            1 => Ok(RuntimeType::RoboRio2),
+            2 => Ok(RuntimeType::Simulation),
+            other => Err(RuntimeTypeError::UnknownCode(other)),
+        }
+    }
+
+    /// Seconds elapsed since the robot most recently became enabled, or `0.0`
+    /// while disabled.
+    ///
+    /// The baseline resets on every enable/disable and autonomous/teleop
+    /// transition, so teams can use this to schedule end-of-match behavior.
+    pub fn match_time() -> HalResult<f64> {
+        let since = ENABLED_SINCE.load(Ordering::Acquire);
+        if since == 0 {
+            return Ok(0.0);
+        }
+        let now = Self::fpga_time()?;
+        Ok(now.saturating_sub(since) as f64 * 1e-6)
+    }
+
+    /// Construct a [`RobotProgram`] and run it, the way WPILib's `StartRobot` does.
+    ///
+    /// Builds the `RobotBase`, builds the user's robot, starts competition, then
+    /// drives the robot's loop inside a `catch_unwind`. If the robot panics or
+    /// fails to initialize, the failure is reported to the Driver Station via
+    /// `HAL_SendError` and a backtrace is printed, rather than the process
+    /// silently aborting.
+    ///
+    /// Some HAL backends require their main loop to be driven from the
+    /// program's primary thread, signaled by `HAL_HasMain`. When that's the
+    /// case, the robot program instead runs on a spawned thread while this
+    /// thread drives `HAL_RunMain`/`HAL_ExitMain`.
+    ///
+    /// Returns a process exit status: `0` on a clean exit, nonzero if the
+    /// robot program quit unexpectedly.
+    pub fn start_robot<R: RobotProgram>() -> i32 {
+        let run = move || -> Result<(), RobotBaseInitError> {
+            let base = RobotBase::new()?;
+            let mut robot = R::new(&base);
+            RobotBase::start_competition();
+            robot.run();
+            Ok(())
+        };
+
+        let outcome = if unsafe { HAL_HasMain() } != 0 {
+            let handle = thread::spawn(move || {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(run));
+                // Unblocks the primary thread's `HAL_RunMain` call below.
+                unsafe { HAL_ExitMain() };
+                outcome
+            });
+            unsafe { HAL_RunMain() };
+            handle
+                .join()
+                .expect("robot thread panicked without being caught")
+        } else {
+            panic::catch_unwind(AssertUnwindSafe(run))
+        };
+
+        match outcome {
+            Ok(Ok(())) => 0,
+            Ok(Err(e)) => {
+                report_crash(&format!("The robot program quit unexpectedly: {}", e));
+                1
+            }
+            Err(payload) => {
+                let message =
+                    format!("The robot program quit unexpectedly: {}", panic_message(&*payload));
+                if PANIC_HOOK_INSTALLED.load(Ordering::Acquire) {
+                    // The panic hook installed in `RobotBase::new` already
+                    // reported this panic, with its own location, to the
+                    // Driver Station; just log locally instead of sending a
+                    // second report.
+                    eprintln!("{}", message);
+                } else {
+                    // The panic happened before `RobotBase::new` got around to
+                    // installing the hook (e.g. during HAL init), so nothing
+                    // has reported it yet.
+                    report_crash(&message);
+                }
+                1
+            }
+        }
+    }
 }
 
 impl Drop for RobotBase {